@@ -1,57 +1,211 @@
+#[cfg(feature = "rayon")]
+mod rayon_impl;
+mod random_state;
+#[cfg(feature = "serde")]
+mod serde_impl;
+mod set;
+mod try_reserve;
+
 use std::borrow::Borrow;
-use std::collections::hash_map::DefaultHasher;
-use std::hash::{Hash, Hasher};
+use std::hash::{BuildHasher, Hash};
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
 use std::mem;
 
-const INITIAL_NBUCKETS: usize = 1;
+pub use random_state::RandomState;
+pub use set::HashSet;
+pub use try_reserve::TryReserveError;
+
+const INITIAL_NBUCKETS: usize = 4;
 
-pub struct HashMap<K, V> {
-    buckets: Vec<Vec<(K, V)>>,
+// Keep items + tombstones at or below 7/8 of capacity, matching the ~87-90% load
+// factor modern std/hashbrown run their tables at.
+const LOAD_FACTOR_NUM: usize = 7;
+const LOAD_FACTOR_DEN: usize = 8;
+
+enum Slot<K, V> {
+    Empty,
+    Deleted,
+    Full { hash: u64, key: K, value: V },
+}
+
+pub struct HashMap<K, V, S = RandomState> {
+    slots: Vec<Slot<K, V>>,
     items: usize,
+    tombstones: usize,
+    hasher: S,
 }
 
-impl<K, V> HashMap<K, V> {
+impl<K, V> HashMap<K, V, RandomState> {
     pub fn new() -> Self {
         HashMap {
-            buckets: Vec::new(),
+            slots: Vec::new(),
             items: 0,
+            tombstones: 0,
+            hasher: RandomState::new(),
+        }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        HashMap::with_capacity_and_hasher(capacity, RandomState::new())
+    }
+}
+
+impl<K, V> Default for HashMap<K, V, RandomState> {
+    fn default() -> Self {
+        HashMap::new()
+    }
+}
+
+impl<K, V, S> HashMap<K, V, S>
+where
+    S: BuildHasher,
+{
+    pub fn with_hasher(hasher: S) -> Self {
+        HashMap {
+            slots: Vec::new(),
+            items: 0,
+            tombstones: 0,
+            hasher,
+        }
+    }
+
+    pub fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
+        let mut map = HashMap::with_hasher(hasher);
+        if capacity > 0 {
+            map.slots = empty_slots(capacity_for(capacity));
+        }
+        map
+    }
+}
+
+// The smallest power-of-two capacity that keeps `capacity` items under the
+// load factor.
+fn capacity_for(capacity: usize) -> usize {
+    let mut nbuckets = INITIAL_NBUCKETS;
+    while capacity > nbuckets * LOAD_FACTOR_NUM / LOAD_FACTOR_DEN {
+        nbuckets *= 2;
+    }
+    nbuckets
+}
+
+// Same as `capacity_for`, but reports overflow instead of panicking, for
+// `try_reserve`'s sake.
+fn try_capacity_for(capacity: usize) -> Result<usize, TryReserveError> {
+    let mut nbuckets = INITIAL_NBUCKETS;
+    loop {
+        let threshold = nbuckets
+            .checked_mul(LOAD_FACTOR_NUM)
+            .map(|scaled| scaled / LOAD_FACTOR_DEN)
+            .ok_or(TryReserveError::CapacityOverflow)?;
+        if capacity <= threshold {
+            return Ok(nbuckets);
         }
+        nbuckets = nbuckets
+            .checked_mul(2)
+            .ok_or(TryReserveError::CapacityOverflow)?;
     }
 }
 
-pub struct OccupiedEntry<'a, K: 'a, V: 'a> {
-    entry: &'a mut (K, V),
+fn empty_slots<K, V>(capacity: usize) -> Vec<Slot<K, V>> {
+    let mut slots = Vec::with_capacity(capacity);
+    slots.extend((0..capacity).map(|_| Slot::Empty));
+    slots
+}
+
+pub struct OccupiedEntry<'a, K: 'a, V: 'a, S: 'a> {
+    index: usize,
+    map: &'a mut HashMap<K, V, S>,
 }
 
-pub struct VacantEntry<'a, K: 'a, V: 'a> {
+impl<'a, K: 'a, V: 'a, S> OccupiedEntry<'a, K, V, S> {
+    pub fn key(&self) -> &K {
+        match &self.map.slots[self.index] {
+            Slot::Full { key, .. } => key,
+            _ => unreachable!("OccupiedEntry always points at a Full slot"),
+        }
+    }
+
+    pub fn get(&self) -> &V {
+        match &self.map.slots[self.index] {
+            Slot::Full { value, .. } => value,
+            _ => unreachable!("OccupiedEntry always points at a Full slot"),
+        }
+    }
+
+    pub fn get_mut(&mut self) -> &mut V {
+        match &mut self.map.slots[self.index] {
+            Slot::Full { value, .. } => value,
+            _ => unreachable!("OccupiedEntry always points at a Full slot"),
+        }
+    }
+
+    pub fn into_mut(self) -> &'a mut V {
+        match &mut self.map.slots[self.index] {
+            Slot::Full { value, .. } => value,
+            _ => unreachable!("OccupiedEntry always points at a Full slot"),
+        }
+    }
+
+    pub fn insert(&mut self, value: V) -> V {
+        mem::replace(self.get_mut(), value)
+    }
+
+    pub fn remove(self) -> V {
+        self.map.items -= 1;
+        self.map.tombstones += 1;
+        match mem::replace(&mut self.map.slots[self.index], Slot::Deleted) {
+            Slot::Full { value, .. } => value,
+            _ => unreachable!("OccupiedEntry always points at a Full slot"),
+        }
+    }
+}
+
+pub struct VacantEntry<'a, K: 'a, V: 'a, S: 'a> {
     key: K,
-    map: &'a mut HashMap<K, V>,
-    bucket: usize,
+    hash: u64,
+    index: usize,
+    map: &'a mut HashMap<K, V, S>,
 }
 
-impl<'a, K: 'a, V: 'a> VacantEntry<'a, K, V> {
-    pub fn insert(self, value: V) -> &'a mut V
-    where
-        K: Hash + Eq,
-    {
-        self.map.buckets[self.bucket].push((self.key, value));
+impl<'a, K: 'a, V: 'a, S> VacantEntry<'a, K, V, S> {
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    pub fn insert(self, value: V) -> &'a mut V {
+        if let Slot::Deleted = self.map.slots[self.index] {
+            self.map.tombstones -= 1;
+        }
+        self.map.slots[self.index] = Slot::Full {
+            hash: self.hash,
+            key: self.key,
+            value,
+        };
         self.map.items += 1;
-        &mut self.map.buckets[self.bucket].last_mut().unwrap().1
+        match &mut self.map.slots[self.index] {
+            Slot::Full { value, .. } => value,
+            _ => unreachable!("just inserted a Full slot"),
+        }
     }
 }
 
-pub enum Entry<'a, K: 'a, V: 'a> {
-    Occupied(OccupiedEntry<'a, K, V>),
-    Vacant(VacantEntry<'a, K, V>),
+pub enum Entry<'a, K: 'a, V: 'a, S: 'a> {
+    Occupied(OccupiedEntry<'a, K, V, S>),
+    Vacant(VacantEntry<'a, K, V, S>),
 }
 
-impl<'a, K, V> Entry<'a, K, V>
+impl<'a, K, V, S> Entry<'a, K, V, S>
 where
     K: Hash + Eq,
 {
     pub fn or_insert(self, value: V) -> &'a mut V {
         match self {
-            Entry::Occupied(e) => &mut e.entry.1,
+            Entry::Occupied(e) => e.into_mut(),
             Entry::Vacant(e) => e.insert(value),
         }
     }
@@ -61,7 +215,7 @@ where
         F: FnOnce() -> V,
     {
         match self {
-            Entry::Occupied(e) => &mut e.entry.1,
+            Entry::Occupied(e) => e.into_mut(),
             Entry::Vacant(e) => e.insert(maker()),
         }
     }
@@ -72,56 +226,152 @@ where
     {
         self.or_insert_with(Default::default)
     }
+
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(e) => e.key(),
+            Entry::Vacant(e) => e.key(),
+        }
+    }
+
+    pub fn and_modify<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut V),
+    {
+        match self {
+            Entry::Occupied(mut e) => {
+                f(e.get_mut());
+                Entry::Occupied(e)
+            }
+            Entry::Vacant(e) => Entry::Vacant(e),
+        }
+    }
+}
+
+// The outcome of probing the table for a key: its hash, where it currently
+// lives (if it's there), and the first tombstone or empty slot seen along
+// the way, which is where an insert should land.
+struct Probe {
+    hash: u64,
+    found: Option<usize>,
+    insert_at: Option<usize>,
 }
 
-impl<K, V> HashMap<K, V>
+impl<K, V, S> HashMap<K, V, S>
 where
     K: Hash + Eq,
+    S: BuildHasher,
 {
-    fn bucket<Q>(&self, key: &Q) -> Option<usize>
+    fn hash<Q>(&self, key: &Q) -> u64
+    where
+        Q: Hash + ?Sized,
+    {
+        self.hasher.hash_one(key)
+    }
+
+    // Quadratic-probes the table for `key`, starting at `hash & mask` and
+    // stepping `i, i+1, i+3, i+6, ...` (triangular numbers) until it hits
+    // either a matching `Full` slot or an `Empty` one.
+    fn probe<Q>(&self, key: &Q) -> Probe
     where
         K: Borrow<Q>,
         Q: Hash + Eq + ?Sized,
     {
-        if self.buckets.is_empty() {
-            return None;
+        let hash = self.hash(key);
+
+        if self.slots.is_empty() {
+            return Probe {
+                hash,
+                found: None,
+                insert_at: None,
+            };
+        }
+
+        let mask = self.slots.len() - 1;
+        let mut index = (hash as usize) & mask;
+        let mut step = 1;
+        let mut insert_at = None;
+
+        loop {
+            match &self.slots[index] {
+                Slot::Empty => {
+                    return Probe {
+                        hash,
+                        found: None,
+                        insert_at: insert_at.or(Some(index)),
+                    };
+                }
+                Slot::Deleted => {
+                    if insert_at.is_none() {
+                        insert_at = Some(index);
+                    }
+                }
+                Slot::Full {
+                    hash: ehash,
+                    key: ekey,
+                    ..
+                } => {
+                    if *ehash == hash && ekey.borrow() == key {
+                        return Probe {
+                            hash,
+                            found: Some(index),
+                            insert_at,
+                        };
+                    }
+                }
+            }
+
+            index = (index + step) & mask;
+            step += 1;
         }
-        let mut hasher = DefaultHasher::new();
-        key.hash(&mut hasher);
-        Some((hasher.finish() % self.buckets.len() as u64) as usize)
     }
 
-    pub fn entry<'a>(&'a mut self, key: K) -> Entry<'a, K, V> {
-        if self.buckets.is_empty() || self.items > 3 * self.buckets.len() / 4 {
+    fn grow_if_needed(&mut self) {
+        if self.slots.is_empty()
+            || self.items + self.tombstones >= self.slots.len() * LOAD_FACTOR_NUM / LOAD_FACTOR_DEN
+        {
             self.resize();
         }
+    }
 
-        let bucket = self.bucket(&key).expect("buckets.is_empty() handled above");
-        match self.buckets[bucket].iter().position(|&(ref ekey, _)| ekey == &key) {
-            Some(index) => Entry::Occupied(OccupiedEntry {
-                entry: &mut self.buckets[bucket][index]
+    pub fn entry<'a>(&'a mut self, key: K) -> Entry<'a, K, V, S> {
+        self.grow_if_needed();
+
+        let probe = self.probe(&key);
+        match probe.found {
+            Some(index) => Entry::Occupied(OccupiedEntry { index, map: self }),
+            None => Entry::Vacant(VacantEntry {
+                key,
+                hash: probe.hash,
+                index: probe.insert_at.expect("table has an empty slot"),
+                map: self,
             }),
-            None => Entry::Vacant(VacantEntry { map: self, key, bucket })
         }
     }
 
     pub fn insert(&mut self, key: K, value: V) -> Option<V> {
-        if self.buckets.is_empty() || self.items > 3 * self.buckets.len() / 4 {
-            self.resize();
-        }
-
-        let bucket = self.bucket(&key).expect("buckets.is_empty() handled above");
-        let bucket = &mut self.buckets[bucket];
-
-        for &mut (ref ekey, ref mut evalue) in bucket.iter_mut() {
-            if ekey == &key {
-                return Some(mem::replace(evalue, value));
+        self.grow_if_needed();
+
+        let probe = self.probe(&key);
+        match probe.found {
+            Some(index) => match &mut self.slots[index] {
+                Slot::Full { value: evalue, .. } => Some(mem::replace(evalue, value)),
+                _ => unreachable!("Probe::found always points at a Full slot"),
+            },
+            None => {
+                let index = probe.insert_at.expect("table has an empty slot");
+                if let Slot::Deleted = self.slots[index] {
+                    self.tombstones -= 1;
+                }
+                self.slots[index] = Slot::Full {
+                    hash: probe.hash,
+                    key,
+                    value,
+                };
+                self.items += 1;
+                None
             }
         }
-
-        self.items += 1;
-        bucket.push((key, value));
-        None
     }
 
     pub fn get<Q>(&self, key: &Q) -> Option<&V>
@@ -129,11 +379,11 @@ where
         K: Borrow<Q>,
         Q: Hash + Eq + ?Sized,
     {
-        let bucket = self.bucket(key)?;
-        self.buckets[bucket]
-            .iter()
-            .find(|&(ref ekey, _)| ekey.borrow() == key)
-            .map(|&(_, ref v)| v)
+        let index = self.probe(key).found?;
+        match &self.slots[index] {
+            Slot::Full { value, .. } => Some(value),
+            _ => unreachable!("Probe::found always points at a Full slot"),
+        }
     }
 
     pub fn contains_key<Q>(&self, key: &Q) -> bool
@@ -149,13 +399,13 @@ where
         K: Borrow<Q>,
         Q: Hash + Eq + ?Sized,
     {
-        let bucket = self.bucket(key)?;
-        let bucket = &mut self.buckets[bucket];
-        let i = bucket
-            .iter()
-            .position(|&(ref ekey, _)| ekey.borrow() == key)?;
+        let index = self.probe(key).found?;
         self.items -= 1;
-        Some(bucket.swap_remove(i).1)
+        self.tombstones += 1;
+        match mem::replace(&mut self.slots[index], Slot::Deleted) {
+            Slot::Full { value, .. } => Some(value),
+            _ => unreachable!("Probe::found always points at a Full slot"),
+        }
     }
 
     pub fn len(&self) -> usize {
@@ -166,29 +416,140 @@ where
         self.items == 0
     }
 
-    fn resize(&mut self) {
-        let target_size = match self.buckets.len() {
-            0 => INITIAL_NBUCKETS,
-            n => 2 * n,
-        };
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            slots: &self.slots,
+            at: 0,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut {
+            slots: &mut self.slots,
+        }
+    }
+
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys { inner: self.iter() }
+    }
+
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values { inner: self.iter() }
+    }
 
-        let mut new_buckets = Vec::with_capacity(target_size);
-        new_buckets.extend((0..target_size).map(|_| Vec::new()));
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        ValuesMut {
+            inner: self.iter_mut(),
+        }
+    }
+
+    /// Removes and returns all entries, leaving the map empty. Any entries
+    /// not consumed from the returned iterator are dropped when it is
+    /// dropped.
+    pub fn drain(&mut self) -> Drain<'_, K, V> {
+        let slots = mem::take(&mut self.slots);
+        self.items = 0;
+        self.tombstones = 0;
+        Drain {
+            inner: slots.into_iter(),
+            _map: PhantomData,
+        }
+    }
+
+    /// Keeps only the entries for which `f` returns `true`, turning the rest
+    /// into tombstones.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        for slot in self.slots.iter_mut() {
+            let keep = match slot {
+                Slot::Full { key, value, .. } => f(key, value),
+                Slot::Empty | Slot::Deleted => continue,
+            };
+            if !keep {
+                *slot = Slot::Deleted;
+                self.items -= 1;
+                self.tombstones += 1;
+            }
+        }
+    }
 
-        for (key, value) in self.buckets.iter_mut().flat_map(|bucket| bucket.drain(..)) {
-            let mut hasher = DefaultHasher::new();
-            key.hash(&mut hasher);
-            let bucket = (hasher.finish() % new_buckets.len() as u64) as usize;
-            new_buckets[bucket].push((key, value));
+    /// Reserves capacity for at least `additional` more elements, panicking
+    /// if the new capacity overflows `usize` or the allocator reports
+    /// failure. See [`try_reserve`](HashMap::try_reserve) for a fallible
+    /// version.
+    pub fn reserve(&mut self, additional: usize) {
+        self.try_reserve(additional)
+            .expect("reserve failed to allocate the requested capacity")
+    }
+
+    /// Tries to reserve capacity for at least `additional` more elements,
+    /// returning `Err` instead of aborting the process if the capacity
+    /// needed overflows `usize` or the allocator can't satisfy the request.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        let needed = self
+            .items
+            .checked_add(additional)
+            .ok_or(TryReserveError::CapacityOverflow)?;
+
+        if !self.slots.is_empty() && self.slots.len() * LOAD_FACTOR_NUM / LOAD_FACTOR_DEN >= needed
+        {
+            return Ok(());
         }
 
-        mem::replace(&mut self.buckets, new_buckets);
+        let target_capacity = try_capacity_for(needed)?;
+        self.try_resize_to(target_capacity)
+    }
+
+    // Rehashes every live entry into a fresh table of `target_capacity`
+    // slots. This also reclaims all tombstones, since only `Full` slots get
+    // carried over.
+    fn try_resize_to(&mut self, target_capacity: usize) -> Result<(), TryReserveError> {
+        let mut new_slots = Vec::new();
+        new_slots
+            .try_reserve_exact(target_capacity)
+            .map_err(|_| TryReserveError::alloc_error::<Slot<K, V>>(target_capacity))?;
+        new_slots.extend((0..target_capacity).map(|_| Slot::Empty));
+
+        let old_slots = mem::replace(&mut self.slots, new_slots);
+        self.tombstones = 0;
+
+        let mask = self.slots.len() - 1;
+        for slot in old_slots {
+            let (hash, key, value) = match slot {
+                Slot::Full { hash, key, value } => (hash, key, value),
+                Slot::Empty | Slot::Deleted => continue,
+            };
+
+            let mut index = (hash as usize) & mask;
+            let mut step = 1;
+            while let Slot::Full { .. } = self.slots[index] {
+                index = (index + step) & mask;
+                step += 1;
+            }
+            self.slots[index] = Slot::Full { hash, key, value };
+        }
+
+        Ok(())
+    }
+
+    fn resize(&mut self) {
+        // Size the new table from the live item count (plus the one item
+        // about to be inserted), not from the current
+        // capacity: `grow_if_needed` also fires when tombstones (not actual
+        // growth) push `items + tombstones` over the load factor, and
+        // doubling unconditionally would let a churn workload (insert a
+        // unique key, remove it, repeat) grow the table forever even though
+        // `items` never increases.
+        let target_capacity = capacity_for(self.items + 1);
+        self.try_resize_to(target_capacity)
+            .expect("resize failed to allocate the target-sized table")
     }
 }
 
 pub struct Iter<'a, K: 'a, V: 'a> {
-    map: &'a HashMap<K, V>,
-    bucket: usize,
+    slots: &'a [Slot<K, V>],
     at: usize,
 }
 
@@ -196,20 +557,14 @@ impl<'a, K, V> Iterator for Iter<'a, K, V> {
     type Item = (&'a K, &'a V);
     fn next(&mut self) -> Option<Self::Item> {
         loop {
-            match self.map.buckets.get(self.bucket) {
-                Some(bucket) => {
-                    match bucket.get(self.at) {
-                        Some(&(ref k, ref v)) => {
-                            // move along self.at and self.bucket
-                            self.at += 1;
-                            break Some((k, v));
-                        }
-                        None => {
-                            self.bucket += 1;
-                            self.at = 0;
-                            continue;
-                        }
-                    }
+            match self.slots.get(self.at) {
+                Some(Slot::Full { key, value, .. }) => {
+                    self.at += 1;
+                    break Some((key, value));
+                }
+                Some(_) => {
+                    self.at += 1;
+                    continue;
                 }
                 None => break None,
             }
@@ -217,54 +572,123 @@ impl<'a, K, V> Iterator for Iter<'a, K, V> {
     }
 }
 
-impl<'a, K, V> IntoIterator for &'a HashMap<K, V> {
+impl<'a, K, V, S> IntoIterator for &'a HashMap<K, V, S> {
     type Item = (&'a K, &'a V);
     type IntoIter = Iter<'a, K, V>;
     fn into_iter(self) -> Self::IntoIter {
         Iter {
-            map: self,
-            bucket: 0,
+            slots: &self.slots,
             at: 0,
         }
     }
 }
 
 pub struct IntoIter<K, V> {
-    map: HashMap<K, V>,
-    bucket: usize,
+    slots: std::vec::IntoIter<Slot<K, V>>,
 }
 
 impl<K, V> Iterator for IntoIter<K, V> {
     type Item = (K, V);
     fn next(&mut self) -> Option<Self::Item> {
         loop {
-            match self.map.buckets.get_mut(self.bucket) {
-                Some(bucket) => match bucket.pop() {
-                    Some(x) => break Some(x),
-                    None => {
-                        self.bucket += 1;
-                        continue;
-                    }
-                },
-                None => break None,
+            match self.slots.next()? {
+                Slot::Full { key, value, .. } => break Some((key, value)),
+                Slot::Empty | Slot::Deleted => continue,
             }
         }
     }
 }
 
-impl<K, V> IntoIterator for HashMap<K, V> {
+impl<K, V, S> IntoIterator for HashMap<K, V, S> {
     type Item = (K, V);
     type IntoIter = IntoIter<K, V>;
     fn into_iter(self) -> Self::IntoIter {
         IntoIter {
-            map: self,
-            bucket: 0,
+            slots: self.slots.into_iter(),
+        }
+    }
+}
+
+pub struct IterMut<'a, K: 'a, V: 'a> {
+    slots: &'a mut [Slot<K, V>],
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            // Take the remaining slice so we can split it, instead of
+            // reborrowing `self.slots` on every iteration.
+            let slots = mem::take(&mut self.slots);
+            let (first, rest) = slots.split_first_mut()?;
+            self.slots = rest;
+            if let Slot::Full { key, value, .. } = first {
+                return Some((&*key, value));
+            }
+        }
+    }
+}
+
+pub struct Keys<'a, K: 'a, V: 'a> {
+    inner: Iter<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for Keys<'a, K, V> {
+    type Item = &'a K;
+    fn next(&mut self) -> Option<&'a K> {
+        self.inner.next().map(|(key, _)| key)
+    }
+}
+
+pub struct Values<'a, K: 'a, V: 'a> {
+    inner: Iter<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for Values<'a, K, V> {
+    type Item = &'a V;
+    fn next(&mut self) -> Option<&'a V> {
+        self.inner.next().map(|(_, value)| value)
+    }
+}
+
+pub struct ValuesMut<'a, K: 'a, V: 'a> {
+    inner: IterMut<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for ValuesMut<'a, K, V> {
+    type Item = &'a mut V;
+    fn next(&mut self) -> Option<&'a mut V> {
+        self.inner.next().map(|(_, value)| value)
+    }
+}
+
+pub struct Drain<'a, K: 'a, V: 'a> {
+    inner: std::vec::IntoIter<Slot<K, V>>,
+    _map: PhantomData<&'a mut ()>,
+}
+
+impl<'a, K, V> Iterator for Drain<'a, K, V> {
+    type Item = (K, V);
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.inner.next()? {
+                Slot::Full { key, value, .. } => break Some((key, value)),
+                Slot::Empty | Slot::Deleted => continue,
+            }
         }
     }
 }
 
+impl<'a, K, V> FusedIterator for Iter<'a, K, V> {}
+impl<K, V> FusedIterator for IntoIter<K, V> {}
+impl<'a, K, V> FusedIterator for IterMut<'a, K, V> {}
+impl<'a, K, V> FusedIterator for Keys<'a, K, V> {}
+impl<'a, K, V> FusedIterator for Values<'a, K, V> {}
+impl<'a, K, V> FusedIterator for ValuesMut<'a, K, V> {}
+impl<'a, K, V> FusedIterator for Drain<'a, K, V> {}
+
 use std::iter::FromIterator;
-impl<K, V> FromIterator<(K, V)> for HashMap<K, V>
+impl<K, V> FromIterator<(K, V)> for HashMap<K, V, RandomState>
 where
     K: Hash + Eq,
 {
@@ -338,4 +762,130 @@ mod tests {
         assert_eq!(map.get("key"), None);
         assert_eq!(map.remove("key"), None);
     }
+
+    #[test]
+    fn resize_reclaims_tombstones() {
+        let mut map = HashMap::new();
+        for i in 0..100 {
+            map.insert(i, i);
+        }
+        for i in 0..100 {
+            if i % 2 == 0 {
+                map.remove(&i);
+            }
+        }
+        for i in 100..200 {
+            map.insert(i, i);
+        }
+        assert_eq!(map.len(), 150);
+        for i in 0..200 {
+            if i < 100 && i % 2 == 0 {
+                assert_eq!(map.get(&i), None);
+            } else {
+                assert_eq!(map.get(&i), Some(&i));
+            }
+        }
+    }
+
+    #[test]
+    fn churn_does_not_grow_the_table_unboundedly() {
+        let mut map = HashMap::new();
+        for i in 0..20_000 {
+            map.insert(i, i);
+            map.remove(&i);
+        }
+        assert_eq!(map.len(), 0);
+        assert!(map.slots.len() <= INITIAL_NBUCKETS * 8);
+    }
+
+    #[test]
+    fn reserve_does_not_lose_items() {
+        let mut map = HashMap::with_capacity(4);
+        map.insert("a", 1);
+        map.reserve(100);
+        map.insert("b", 2);
+        assert_eq!(map.get("a"), Some(&1));
+        assert_eq!(map.get("b"), Some(&2));
+        assert_eq!(map.len(), 2);
+    }
+
+    #[test]
+    fn try_reserve_reports_capacity_overflow() {
+        let mut map: HashMap<u8, u8> = HashMap::new();
+        assert_eq!(
+            map.try_reserve(usize::MAX),
+            Err(TryReserveError::CapacityOverflow)
+        );
+    }
+
+    #[test]
+    fn iter_mut_and_values_mut() {
+        let mut map = HashMap::new();
+        map.insert("foo", 1);
+        map.insert("bar", 2);
+        for (_, v) in map.iter_mut() {
+            *v *= 10;
+        }
+        for v in map.values_mut() {
+            *v += 1;
+        }
+        let mut values: Vec<_> = map.values().copied().collect();
+        values.sort();
+        assert_eq!(values, vec![11, 21]);
+
+        let mut keys: Vec<_> = map.keys().copied().collect();
+        keys.sort();
+        assert_eq!(keys, vec!["bar", "foo"]);
+    }
+
+    #[test]
+    fn drain_empties_the_map() {
+        let mut map = HashMap::new();
+        map.insert("foo", 1);
+        map.insert("bar", 2);
+        let mut drained: Vec<_> = map.drain().collect();
+        drained.sort();
+        assert_eq!(drained, vec![("bar", 2), ("foo", 1)]);
+        assert!(map.is_empty());
+        assert_eq!(map.get("foo"), None);
+    }
+
+    #[test]
+    fn retain_drops_entries() {
+        let mut map: HashMap<i32, i32> = (0..10).map(|i| (i, i)).collect();
+        map.retain(|_, v| *v % 2 == 0);
+        assert_eq!(map.len(), 5);
+        for i in 0..10 {
+            assert_eq!(map.get(&i), if i % 2 == 0 { Some(&i) } else { None });
+        }
+    }
+
+    #[test]
+    fn entry_and_modify_counts() {
+        let mut counts: HashMap<&str, i32> = HashMap::new();
+        for word in ["a", "b", "a", "c", "b", "a"] {
+            counts.entry(word).and_modify(|c| *c += 1).or_insert(1);
+        }
+        assert_eq!(counts.get("a"), Some(&3));
+        assert_eq!(counts.get("b"), Some(&2));
+        assert_eq!(counts.get("c"), Some(&1));
+    }
+
+    #[test]
+    fn entry_key_and_occupied_methods() {
+        let mut map = HashMap::new();
+        assert_eq!(map.entry("foo").key(), &"foo");
+        map.insert("foo", 1);
+
+        match map.entry("foo") {
+            Entry::Occupied(mut e) => {
+                assert_eq!(e.key(), &"foo");
+                assert_eq!(e.get(), &1);
+                assert_eq!(e.insert(2), 1);
+                assert_eq!(e.remove(), 2);
+            }
+            Entry::Vacant(_) => panic!("expected an occupied entry"),
+        }
+        assert_eq!(map.get("foo"), None);
+    }
 }
@@ -0,0 +1,83 @@
+use std::cell::Cell;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+thread_local!(static SEED_COUNTER: Cell<u64> = const { Cell::new(0) });
+
+// Pulls in a bit of OS/thread-local entropy so that two `RandomState`s
+// created back to back (even on different threads) don't collide, without
+// pulling in a full CSPRNG dependency just for this.
+fn next_seed() -> u64 {
+    SEED_COUNTER.with(|counter| {
+        let mut seed = counter.get().wrapping_add(1);
+        counter.set(seed);
+
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0);
+        seed ^= nanos;
+        seed ^= counter as *const _ as u64;
+        seed
+    })
+}
+
+/// A `BuildHasher` that seeds every `Hasher` it creates with a value that
+/// differs per `RandomState` (and thus, typically, per `HashMap`), so that
+/// the bucket order of two maps holding the same keys differs and an
+/// attacker who can observe iteration order of one map can't use it to
+/// predict another's, the way it could if every map used the same fixed
+/// `DefaultHasher` seed (the classic HashDoS collision-flooding attack).
+#[derive(Clone)]
+pub struct RandomState {
+    seed: u64,
+}
+
+impl RandomState {
+    pub fn new() -> Self {
+        RandomState { seed: next_seed() }
+    }
+}
+
+impl Default for RandomState {
+    fn default() -> Self {
+        RandomState::new()
+    }
+}
+
+impl BuildHasher for RandomState {
+    type Hasher = SeededHasher;
+    fn build_hasher(&self) -> SeededHasher {
+        // Mix the seed into the hasher's internal state before any key bytes
+        // are written, rather than folding it into the finished digest: the
+        // latter would be a no-op against an attacker who has precomputed a
+        // collision for the fixed-key `DefaultHasher`, since XOR-ing a
+        // constant onto the output preserves equality between any two
+        // digests. Feeding the seed through `write_u64` first means the rest
+        // of the hash is computed starting from per-map internal state, so a
+        // collision precomputed against a plain `DefaultHasher` doesn't
+        // transfer.
+        let mut inner = DefaultHasher::new();
+        inner.write_u64(self.seed);
+        SeededHasher { inner }
+    }
+}
+
+/// Wraps the standard library's `DefaultHasher`, having already absorbed a
+/// per-map seed into its internal state so that the seed affects bucket
+/// placement without us having to reimplement SipHash just to pass it custom
+/// keys.
+pub struct SeededHasher {
+    inner: DefaultHasher,
+}
+
+impl Hasher for SeededHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        self.inner.write(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        self.inner.finish()
+    }
+}
@@ -0,0 +1,240 @@
+use std::hash::{BuildHasher, Hash};
+use std::marker::PhantomData;
+use std::mem;
+
+use rayon::iter::plumbing::UnindexedConsumer;
+use rayon::prelude::*;
+
+use crate::{HashMap, Slot};
+
+/// A parallel iterator over `&HashMap`'s entries, partitioned over
+/// contiguous ranges of the backing slot array the same way
+/// `rayon::slice::Iter` partitions a plain slice.
+pub struct ParIter<'a, K: 'a, V: 'a> {
+    slots: &'a [Slot<K, V>],
+}
+
+impl<'a, K, V> ParallelIterator for ParIter<'a, K, V>
+where
+    K: Sync + 'a,
+    V: Sync + 'a,
+{
+    type Item = (&'a K, &'a V);
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        self.slots
+            .par_iter()
+            .filter_map(|slot| match slot {
+                Slot::Full { key, value, .. } => Some((key, value)),
+                Slot::Empty | Slot::Deleted => None,
+            })
+            .drive_unindexed(consumer)
+    }
+}
+
+impl<'a, K, V, S> IntoParallelRefIterator<'a> for HashMap<K, V, S>
+where
+    K: Sync + 'a,
+    V: Sync + 'a,
+{
+    type Iter = ParIter<'a, K, V>;
+    type Item = (&'a K, &'a V);
+
+    fn par_iter(&'a self) -> Self::Iter {
+        ParIter { slots: &self.slots }
+    }
+}
+
+/// Like [`ParIter`], but yielding mutable references to the values.
+pub struct ParIterMut<'a, K: 'a, V: 'a> {
+    slots: &'a mut [Slot<K, V>],
+}
+
+impl<'a, K, V> ParallelIterator for ParIterMut<'a, K, V>
+where
+    K: Send + Sync + 'a,
+    V: Send + 'a,
+{
+    type Item = (&'a K, &'a mut V);
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        self.slots
+            .par_iter_mut()
+            .filter_map(|slot| match slot {
+                Slot::Full { key, value, .. } => Some((&*key, value)),
+                Slot::Empty | Slot::Deleted => None,
+            })
+            .drive_unindexed(consumer)
+    }
+}
+
+impl<'a, K, V, S> IntoParallelRefMutIterator<'a> for HashMap<K, V, S>
+where
+    K: Send + Sync + 'a,
+    V: Send + 'a,
+{
+    type Iter = ParIterMut<'a, K, V>;
+    type Item = (&'a K, &'a mut V);
+
+    fn par_iter_mut(&'a mut self) -> Self::Iter {
+        ParIterMut {
+            slots: &mut self.slots,
+        }
+    }
+}
+
+/// A parallel iterator over a `HashMap`'s owned entries.
+pub struct IntoParIter<K, V> {
+    slots: Vec<Slot<K, V>>,
+}
+
+impl<K, V> ParallelIterator for IntoParIter<K, V>
+where
+    K: Send,
+    V: Send,
+{
+    type Item = (K, V);
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        self.slots
+            .into_par_iter()
+            .filter_map(|slot| match slot {
+                Slot::Full { key, value, .. } => Some((key, value)),
+                Slot::Empty | Slot::Deleted => None,
+            })
+            .drive_unindexed(consumer)
+    }
+}
+
+impl<K, V, S> IntoParallelIterator for HashMap<K, V, S>
+where
+    K: Send,
+    V: Send,
+{
+    type Iter = IntoParIter<K, V>;
+    type Item = (K, V);
+
+    fn into_par_iter(self) -> Self::Iter {
+        IntoParIter { slots: self.slots }
+    }
+}
+
+/// A parallel draining iterator; like [`IntoParIter`], but leaves the map
+/// empty instead of consuming it.
+pub struct ParDrain<'a, K: 'a, V: 'a> {
+    slots: Vec<Slot<K, V>>,
+    marker: PhantomData<&'a mut ()>,
+}
+
+impl<'a, K, V> ParallelIterator for ParDrain<'a, K, V>
+where
+    K: Send,
+    V: Send,
+{
+    type Item = (K, V);
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        self.slots
+            .into_par_iter()
+            .filter_map(|slot| match slot {
+                Slot::Full { key, value, .. } => Some((key, value)),
+                Slot::Empty | Slot::Deleted => None,
+            })
+            .drive_unindexed(consumer)
+    }
+}
+
+impl<K, V, S> HashMap<K, V, S>
+where
+    K: Send,
+    V: Send,
+{
+    pub fn par_drain(&mut self) -> ParDrain<'_, K, V> {
+        let slots = mem::take(&mut self.slots);
+        self.items = 0;
+        self.tombstones = 0;
+        ParDrain {
+            slots,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<K, V, S> ParallelExtend<(K, V)> for HashMap<K, V, S>
+where
+    K: Eq + Hash + Send,
+    V: Send,
+    S: BuildHasher + Send,
+{
+    fn par_extend<I>(&mut self, par_iter: I)
+    where
+        I: IntoParallelIterator<Item = (K, V)>,
+    {
+        // The table's probe sequence isn't safe to mutate from multiple
+        // threads at once, so collect in parallel and insert sequentially.
+        let items: Vec<(K, V)> = par_iter.into_par_iter().collect();
+        self.reserve(items.len());
+        for (key, value) in items {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K, V, S> FromParallelIterator<(K, V)> for HashMap<K, V, S>
+where
+    K: Eq + Hash + Send,
+    V: Send,
+    S: BuildHasher + Default + Send,
+{
+    fn from_par_iter<I>(par_iter: I) -> Self
+    where
+        I: IntoParallelIterator<Item = (K, V)>,
+    {
+        let mut map = HashMap::with_hasher(S::default());
+        map.par_extend(par_iter);
+        map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RandomState;
+
+    #[test]
+    fn par_iter_sees_every_entry() {
+        let map: HashMap<i32, i32, RandomState> = (0..1000).map(|i| (i, i * 2)).collect();
+        let sum: i64 = map.par_iter().map(|(_, &v)| v as i64).sum();
+        assert_eq!(sum, (0..1000).map(|i| i * 2).sum::<i64>());
+    }
+
+    #[test]
+    fn from_par_iter_round_trips() {
+        let map: HashMap<i32, i32, RandomState> =
+            (0..1000).into_par_iter().map(|i| (i, i)).collect();
+        assert_eq!(map.len(), 1000);
+        for i in 0..1000 {
+            assert_eq!(map.get(&i), Some(&i));
+        }
+    }
+
+    #[test]
+    fn par_drain_empties_the_map() {
+        let mut map: HashMap<i32, i32, RandomState> = (0..1000).map(|i| (i, i)).collect();
+        let drained: i64 = map.par_drain().map(|(k, _)| k as i64).sum();
+        assert_eq!(drained, (0..1000).sum::<i64>());
+        assert!(map.is_empty());
+    }
+}
@@ -0,0 +1,91 @@
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+use std::marker::PhantomData;
+
+use serde::de::{Deserialize, Deserializer, MapAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+use crate::HashMap;
+
+impl<K, V, S> Serialize for HashMap<K, V, S>
+where
+    K: Serialize + Eq + Hash,
+    V: Serialize,
+    S: BuildHasher,
+{
+    fn serialize<T>(&self, serializer: T) -> Result<T::Ok, T::Error>
+    where
+        T: Serializer,
+    {
+        serializer.collect_map(self)
+    }
+}
+
+impl<'de, K, V, S> Deserialize<'de> for HashMap<K, V, S>
+where
+    K: Deserialize<'de> + Eq + Hash,
+    V: Deserialize<'de>,
+    S: BuildHasher + Default,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct MapVisitor<K, V, S> {
+            marker: PhantomData<HashMap<K, V, S>>,
+        }
+
+        impl<'de, K, V, S> Visitor<'de> for MapVisitor<K, V, S>
+        where
+            K: Deserialize<'de> + Eq + Hash,
+            V: Deserialize<'de>,
+            S: BuildHasher + Default,
+        {
+            type Value = HashMap<K, V, S>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a map")
+            }
+
+            fn visit_map<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut map = HashMap::with_capacity_and_hasher(
+                    access.size_hint().unwrap_or(0),
+                    S::default(),
+                );
+                while let Some((key, value)) = access.next_entry()? {
+                    map.insert(key, value);
+                }
+                Ok(map)
+            }
+        }
+
+        deserializer.deserialize_map(MapVisitor {
+            marker: PhantomData,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RandomState;
+
+    #[test]
+    fn round_trips_through_json() {
+        let mut map: HashMap<String, i32, RandomState> = HashMap::new();
+        map.insert("foo".to_string(), 1);
+        map.insert("bar".to_string(), 2);
+
+        let json = serde_json::to_string(&map).unwrap();
+        let round_tripped: HashMap<String, i32, RandomState> =
+            serde_json::from_str(&json).unwrap();
+
+        assert_eq!(round_tripped.len(), map.len());
+        for (k, v) in &map {
+            assert_eq!(round_tripped.get(k.as_str()), Some(v));
+        }
+    }
+}
@@ -0,0 +1,284 @@
+use std::borrow::Borrow;
+use std::hash::{BuildHasher, Hash};
+use std::iter::{Chain, FromIterator};
+
+use crate::{HashMap, IntoIter as MapIntoIter, Iter as MapIter, RandomState};
+
+/// A hash set, implemented as a `HashMap<T, ()>`, reusing all of its
+/// bucket/probe machinery.
+pub struct HashSet<T, S = RandomState> {
+    map: HashMap<T, (), S>,
+}
+
+impl<T> HashSet<T, RandomState> {
+    pub fn new() -> Self {
+        HashSet { map: HashMap::new() }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        HashSet {
+            map: HashMap::with_capacity(capacity),
+        }
+    }
+}
+
+impl<T> Default for HashSet<T, RandomState> {
+    fn default() -> Self {
+        HashSet::new()
+    }
+}
+
+impl<T, S> HashSet<T, S>
+where
+    S: BuildHasher,
+{
+    pub fn with_hasher(hasher: S) -> Self {
+        HashSet {
+            map: HashMap::with_hasher(hasher),
+        }
+    }
+
+    pub fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
+        HashSet {
+            map: HashMap::with_capacity_and_hasher(capacity, hasher),
+        }
+    }
+}
+
+impl<T, S> HashSet<T, S>
+where
+    T: Hash + Eq,
+    S: BuildHasher,
+{
+    pub fn insert(&mut self, value: T) -> bool {
+        self.map.insert(value, ()).is_none()
+    }
+
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.contains_key(value)
+    }
+
+    pub fn remove<Q>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.remove(value).is_some()
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            inner: (&self.map).into_iter(),
+        }
+    }
+
+    pub fn union<'a>(&'a self, other: &'a HashSet<T, S>) -> Union<'a, T, S> {
+        Union {
+            iter: self.iter().chain(other.difference(self)),
+        }
+    }
+
+    pub fn intersection<'a>(&'a self, other: &'a HashSet<T, S>) -> Intersection<'a, T, S> {
+        Intersection {
+            iter: self.iter(),
+            other,
+        }
+    }
+
+    pub fn difference<'a>(&'a self, other: &'a HashSet<T, S>) -> Difference<'a, T, S> {
+        Difference {
+            iter: self.iter(),
+            other,
+        }
+    }
+
+    pub fn symmetric_difference<'a>(
+        &'a self,
+        other: &'a HashSet<T, S>,
+    ) -> SymmetricDifference<'a, T, S> {
+        SymmetricDifference {
+            iter: self.difference(other).chain(other.difference(self)),
+        }
+    }
+}
+
+impl<T> FromIterator<T> for HashSet<T, RandomState>
+where
+    T: Hash + Eq,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut set = HashSet::new();
+        for value in iter {
+            set.insert(value);
+        }
+        set
+    }
+}
+
+pub struct Iter<'a, T: 'a> {
+    inner: MapIter<'a, T, ()>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<&'a T> {
+        self.inner.next().map(|(key, _)| key)
+    }
+}
+
+pub struct IntoIter<T> {
+    inner: MapIntoIter<T, ()>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<T> {
+        self.inner.next().map(|(key, _)| key)
+    }
+}
+
+impl<T, S> IntoIterator for HashSet<T, S> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter {
+            inner: self.map.into_iter(),
+        }
+    }
+}
+
+impl<'a, T, S> IntoIterator for &'a HashSet<T, S>
+where
+    T: Hash + Eq,
+    S: BuildHasher,
+{
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+pub struct Intersection<'a, T: 'a, S: 'a> {
+    iter: Iter<'a, T>,
+    other: &'a HashSet<T, S>,
+}
+
+impl<'a, T, S> Iterator for Intersection<'a, T, S>
+where
+    T: Hash + Eq,
+    S: BuildHasher,
+{
+    type Item = &'a T;
+    fn next(&mut self) -> Option<&'a T> {
+        loop {
+            let item = self.iter.next()?;
+            if self.other.contains(item) {
+                return Some(item);
+            }
+        }
+    }
+}
+
+pub struct Difference<'a, T: 'a, S: 'a> {
+    iter: Iter<'a, T>,
+    other: &'a HashSet<T, S>,
+}
+
+impl<'a, T, S> Iterator for Difference<'a, T, S>
+where
+    T: Hash + Eq,
+    S: BuildHasher,
+{
+    type Item = &'a T;
+    fn next(&mut self) -> Option<&'a T> {
+        loop {
+            let item = self.iter.next()?;
+            if !self.other.contains(item) {
+                return Some(item);
+            }
+        }
+    }
+}
+
+pub struct Union<'a, T: 'a, S: 'a> {
+    iter: Chain<Iter<'a, T>, Difference<'a, T, S>>,
+}
+
+impl<'a, T, S> Iterator for Union<'a, T, S>
+where
+    T: Hash + Eq,
+    S: BuildHasher,
+{
+    type Item = &'a T;
+    fn next(&mut self) -> Option<&'a T> {
+        self.iter.next()
+    }
+}
+
+pub struct SymmetricDifference<'a, T: 'a, S: 'a> {
+    iter: Chain<Difference<'a, T, S>, Difference<'a, T, S>>,
+}
+
+impl<'a, T, S> Iterator for SymmetricDifference<'a, T, S>
+where
+    T: Hash + Eq,
+    S: BuildHasher,
+{
+    type Item = &'a T;
+    fn next(&mut self) -> Option<&'a T> {
+        self.iter.next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_contains_remove() {
+        let mut set = HashSet::new();
+        assert!(set.insert("foo"));
+        assert!(!set.insert("foo"));
+        assert!(set.contains("foo"));
+        assert!(set.remove("foo"));
+        assert!(!set.contains("foo"));
+    }
+
+    #[test]
+    fn set_algebra() {
+        let a: HashSet<i32> = [1, 2, 3].iter().copied().collect();
+        let b: HashSet<i32> = [2, 3, 4].iter().copied().collect();
+
+        let mut union: Vec<_> = a.union(&b).copied().collect();
+        union.sort();
+        assert_eq!(union, vec![1, 2, 3, 4]);
+
+        let mut intersection: Vec<_> = a.intersection(&b).copied().collect();
+        intersection.sort();
+        assert_eq!(intersection, vec![2, 3]);
+
+        let mut difference: Vec<_> = a.difference(&b).copied().collect();
+        difference.sort();
+        assert_eq!(difference, vec![1]);
+
+        let mut symmetric_difference: Vec<_> = a.symmetric_difference(&b).copied().collect();
+        symmetric_difference.sort();
+        assert_eq!(symmetric_difference, vec![1, 4]);
+    }
+}
@@ -0,0 +1,42 @@
+use std::alloc::Layout;
+use std::error::Error;
+use std::fmt;
+
+/// The error type returned by [`HashMap::try_reserve`](crate::HashMap::try_reserve)
+/// when it can't grow the table, instead of aborting the process the way an
+/// infallible `reserve` would.
+#[derive(Debug, PartialEq, Eq)]
+pub enum TryReserveError {
+    /// The requested capacity, or an internal computation derived from it,
+    /// would overflow `usize`.
+    CapacityOverflow,
+    /// The allocator returned an error when asked for memory with the given
+    /// layout.
+    AllocError { layout: Layout },
+}
+
+impl TryReserveError {
+    pub(crate) fn alloc_error<T>(capacity: usize) -> Self {
+        match Layout::array::<T>(capacity) {
+            Ok(layout) => TryReserveError::AllocError { layout },
+            Err(_) => TryReserveError::CapacityOverflow,
+        }
+    }
+}
+
+impl fmt::Display for TryReserveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryReserveError::CapacityOverflow => {
+                write!(f, "memory allocation failed because the computed capacity exceeded `usize::MAX`")
+            }
+            TryReserveError::AllocError { layout } => write!(
+                f,
+                "memory allocation of {} bytes failed",
+                layout.size()
+            ),
+        }
+    }
+}
+
+impl Error for TryReserveError {}